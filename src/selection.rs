@@ -33,14 +33,91 @@ pub enum Selection {
     /// No current selection or start of a selection
     Empty,
 
+    /// A contiguous, wrap-aware stream selection
     Active {
-        start: Point,
-        end: Point,
+        start: Anchor,
+        end: Anchor,
+        start_side: Side,
+        end_side: Side,
+        ty: SelectionType,
+    },
+
+    /// A rectangular selection bounded by the columns of its two endpoints
+    ///
+    /// Unlike `Active`, a `Block` selection never wraps at the end of a
+    /// line; it selects the same range of columns on every covered line.
+    Block {
+        start: Anchor,
+        end: Anchor,
         start_side: Side,
         end_side: Side
     },
 }
 
+/// A selection endpoint anchored to the scrollback buffer, not the viewport
+///
+/// `line` is counted the same way as a viewport `Point`'s (0 at the top of
+/// the screen), but is signed and has no upper bound: `rotate` shifts it as
+/// the grid scrolls, so it keeps pointing at the same text even after lines
+/// are pushed into or out of the scrollback. Once it falls outside the
+/// currently visible rows, `resolve` clamps it to the nearest visible row.
+#[derive(Debug, Clone, Copy)]
+pub struct Anchor {
+    line: isize,
+    col: Column,
+}
+
+impl Anchor {
+    fn new(point: Point) -> Anchor {
+        Anchor { line: point.line.0 as isize, col: point.col }
+    }
+
+    /// Shift this anchor as the grid scrolls
+    ///
+    /// `lines` is the number of rows that scrolled past the top of the
+    /// screen; negative values mean the viewport scrolled the other way.
+    fn rotate(&mut self, lines: isize) {
+        self.line -= lines;
+    }
+
+    /// Resolve a pair of anchors into on-screen points
+    ///
+    /// Each endpoint's line is clamped into the visible `[0, screen_lines)`
+    /// range, so a selection that's only partially scrolled off screen
+    /// still yields the points bounding its visible portion. Returns `None`
+    /// only when both endpoints lie off the same edge, meaning none of the
+    /// selection is currently on screen.
+    fn resolve(start: &Anchor, end: &Anchor, screen_lines: Line) -> Option<(Point, Point)> {
+        let max_line = screen_lines.0 as isize - 1;
+
+        let off_top = start.line < 0 && end.line < 0;
+        let off_bottom = start.line > max_line && end.line > max_line;
+        if off_top || off_bottom {
+            return None;
+        }
+
+        let clamp = |anchor: &Anchor| Point {
+            line: Line(anchor.line.max(0).min(max_line) as usize),
+            col: anchor.col,
+        };
+
+        Some((clamp(start), clamp(end)))
+    }
+}
+
+/// The granularity at which an `Active` selection grows
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SelectionType {
+    /// Plain character-by-character stream selection
+    Simple,
+
+    /// Expands to whole words, as triggered by a double-click
+    Semantic,
+
+    /// Expands to whole visual lines, as triggered by a triple-click
+    Lines,
+}
+
 impl Default for Selection {
     fn default() -> Selection {
         Selection::Empty
@@ -59,6 +136,28 @@ impl Selection {
         mem::replace(self, Selection::Empty);
     }
 
+    /// Start a semantic (word) selection at `location`, as on a double-click
+    pub fn semantic(location: Point) -> Selection {
+        Selection::Active {
+            start: Anchor::new(location),
+            end: Anchor::new(location),
+            start_side: Side::Left,
+            end_side: Side::Left,
+            ty: SelectionType::Semantic,
+        }
+    }
+
+    /// Start a line selection at `location`, as on a triple-click
+    pub fn lines(location: Point) -> Selection {
+        Selection::Active {
+            start: Anchor::new(location),
+            end: Anchor::new(location),
+            start_side: Side::Left,
+            end_side: Side::Left,
+            ty: SelectionType::Lines,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         match *self {
             Selection::Empty => true,
@@ -66,24 +165,93 @@ impl Selection {
         }
     }
 
+    /// Shift the stored endpoints as the grid scrolls
+    ///
+    /// Call this whenever lines are rotated into or out of the active
+    /// region (e.g. new output pushing old lines into the scrollback) so
+    /// the selection stays anchored to the same text instead of the same
+    /// screen coordinates.
+    pub fn rotate(&mut self, lines: isize) {
+        match *self {
+            Selection::Active { ref mut start, ref mut end, .. } |
+            Selection::Block { ref mut start, ref mut end, .. } => {
+                start.rotate(lines);
+                end.rotate(lines);
+            },
+            Selection::Empty => {}
+        }
+    }
+
     pub fn update(&mut self, location: Point, side: Side) {
         let selection = mem::replace(self, Selection::Empty);
         let selection = match selection {
             Selection::Empty => {
                 // Start a selection
                 Selection::Active {
-                    start: location,
-                    end: location,
+                    start: Anchor::new(location),
+                    end: Anchor::new(location),
+                    start_side: side,
+                    end_side: side,
+                    ty: SelectionType::Simple,
+                }
+            },
+            Selection::Active { start, start_side, ty, .. } => {
+                // Update ends; granularity carries over from the start of the drag
+                Selection::Active {
+                    start: start,
+                    start_side: start_side,
+                    end: Anchor::new(location),
+                    end_side: side,
+                    ty: ty,
+                }
+            },
+            Selection::Block { start, start_side, .. } => {
+                // Switched out of block mode; keep the same anchor
+                Selection::Active {
+                    start: start,
+                    start_side: start_side,
+                    end: Anchor::new(location),
+                    end_side: side,
+                    ty: SelectionType::Simple,
+                }
+            }
+        };
+
+        mem::replace(self, selection);
+    }
+
+    /// Update a block (rectangular) selection
+    ///
+    /// This is the block-mode counterpart to `update`; it's used when the
+    /// drag started (or continues) with the block-select modifier, e.g.
+    /// Alt, held down.
+    pub fn update_block(&mut self, location: Point, side: Side) {
+        let selection = mem::replace(self, Selection::Empty);
+        let selection = match selection {
+            Selection::Empty => {
+                // Start a block selection
+                Selection::Block {
+                    start: Anchor::new(location),
+                    end: Anchor::new(location),
                     start_side: side,
                     end_side: side
                 }
             },
             Selection::Active { start, start_side, .. } => {
+                // Switched into block mode; keep the same anchor
+                Selection::Block {
+                    start: start,
+                    start_side: start_side,
+                    end: Anchor::new(location),
+                    end_side: side
+                }
+            },
+            Selection::Block { start, start_side, .. } => {
                 // Update ends
-                Selection::Active {
+                Selection::Block {
                     start: start,
                     start_side: start_side,
-                    end: location,
+                    end: Anchor::new(location),
                     end_side: side
                 }
             }
@@ -92,69 +260,252 @@ impl Selection {
         mem::replace(self, selection);
     }
 
-    pub fn span(&self) -> Option<Span> {
+    /// Compute the selected `Span`, ignoring cell contents
+    ///
+    /// This is a convenience wrapper around `span_with` for `Simple` and
+    /// `Lines` selections, neither of which needs to inspect the grid to
+    /// decide where the selection's edges fall.
+    pub fn span(&self, screen_lines: Line, cols: Column) -> Option<Span> {
+        self.span_with(screen_lines, cols, |_| false)
+    }
+
+    /// Compute the selected `Span`
+    ///
+    /// `screen_lines` is the number of rows currently on screen; it's used
+    /// to translate the selection's buffer-anchored endpoints back into
+    /// viewport points, returning `None` if the whole selection has
+    /// scrolled out of view. `is_separator` identifies cells that delimit a
+    /// word; it's only consulted for `Semantic` selections, which keeps
+    /// this module free of any knowledge of actual grid contents.
+    pub fn span_with<F: Fn(Point) -> bool>(
+        &self,
+        screen_lines: Line,
+        cols: Column,
+        is_separator: F,
+    ) -> Option<Span> {
+        match *self {
+            Selection::Active { ref start, ref end, ref start_side, ref end_side, ref ty } => {
+                let (start_pt, end_pt) = match Anchor::resolve(start, end, screen_lines) {
+                    Some(points) => points,
+                    None => return None
+                };
+
+                // An endpoint whose line has scrolled off screen no longer
+                // has a meaningful column; snap it to the edge of the line
+                // instead, so the still-visible part of the selection is
+                // highlighted (or expanded from) in full, rather than using
+                // a stale column that belonged to a different, now-hidden
+                // line.
+                let max_line = screen_lines.0 as isize - 1;
+                let snap = |anchor: &Anchor, point: Point, side: Side| -> (Point, Side) {
+                    if anchor.line < 0 {
+                        (Point { line: point.line, col: Column(0) }, Side::Left)
+                    } else if anchor.line > max_line {
+                        (Point { line: point.line, col: cols - 1 }, Side::Right)
+                    } else {
+                        (point, side)
+                    }
+                };
+                let (start_pt, start_side) = snap(start, start_pt, *start_side);
+                let (end_pt, end_side) = snap(end, end_pt, *end_side);
+
+                match *ty {
+                    SelectionType::Simple => {
+                        let (front, tail, front_side, tail_side) = if start_pt > end_pt {
+                            // Selected upward; start/end are swapped
+                            (end_pt, start_pt, end_side, start_side)
+                        } else {
+                            // Selected downward; no swapping
+                            (start_pt, end_pt, start_side, end_side)
+                        };
+
+                        debug_assert!(!(tail < front));
+
+                        // Single-cell selections are a special case
+                        if start_pt == end_pt {
+                            if start_side != end_side {
+                                return Some(Span {
+                                    ty: SpanType::Inclusive,
+                                    front,
+                                    tail
+                                });
+                            } else {
+                                return None;
+                            }
+                        }
+
+                        // The other special case is two adjacent cells with no
+                        // selection: [ B][E ] or [ E][B ]
+                        let adjacent = tail.line == front.line && tail.col - front.col == Column(1);
+                        if adjacent && front_side == Side::Right && tail_side == Side::Left {
+                            return None;
+                        }
+
+                        Some(match (front_side, tail_side) {
+                            // [FX][XX][XT]
+                            (Side::Left, Side::Right) => Span {
+                                front,
+                                tail,
+                                ty: SpanType::Inclusive
+                            },
+                            // [ F][XX][T ]
+                            (Side::Right, Side::Left) => Span {
+                                front,
+                                tail,
+                                ty: SpanType::Exclusive
+                            },
+                            // [FX][XX][T ]
+                            (Side::Left, Side::Left) => Span {
+                                front,
+                                tail,
+                                ty: SpanType::ExcludeTail
+                            },
+                            // [ F][XX][XT]
+                            (Side::Right, Side::Right) => Span {
+                                front,
+                                tail,
+                                ty: SpanType::ExcludeFront
+                            },
+                        })
+                    },
+                    SelectionType::Lines => {
+                        let (top, bottom) = if start_pt.line <= end_pt.line {
+                            (start_pt.line, end_pt.line)
+                        } else {
+                            (end_pt.line, start_pt.line)
+                        };
+
+                        Some(Span {
+                            front: Point { line: top, col: Column(0) },
+                            tail: Point { line: bottom, col: cols - 1 },
+                            ty: SpanType::Inclusive,
+                        })
+                    },
+                    SelectionType::Semantic => {
+                        let (start_front, start_tail) =
+                            Selection::semantic_word(start_pt, cols, &is_separator);
+                        let (end_front, end_tail) =
+                            Selection::semantic_word(end_pt, cols, &is_separator);
+
+                        let front = if start_front < end_front { start_front } else { end_front };
+                        let tail = if start_tail > end_tail { start_tail } else { end_tail };
+
+                        Some(Span { front, tail, ty: SpanType::Inclusive })
+                    },
+                }
+            },
+            Selection::Block { .. } | Selection::Empty => None
+        }
+    }
+
+    /// Expand a single point outward to the word it sits in
+    ///
+    /// Stops at the grid edges or the first cell on either side for which
+    /// `is_separator` returns true. A point that is itself a separator
+    /// expands to just that one cell.
+    fn semantic_word<F: Fn(Point) -> bool>(
+        point: Point,
+        cols: Column,
+        is_separator: &F,
+    ) -> (Point, Point) {
+        if is_separator(point) {
+            return (point, point);
+        }
+
+        let mut front = point;
+        while front.col > Column(0) {
+            let next = Point { line: front.line, col: front.col - 1 };
+            if is_separator(next) {
+                break;
+            }
+            front = next;
+        }
+
+        let mut tail = point;
+        while tail.col < cols - 1 {
+            let next = Point { line: tail.line, col: tail.col + 1 };
+            if is_separator(next) {
+                break;
+            }
+            tail = next;
+        }
+
+        (front, tail)
+    }
+
+    /// Compute the selected region of a block (rectangular) selection
+    ///
+    /// Returns `None` for degenerate blocks, e.g. when the two endpoints
+    /// sit between the same pair of adjacent columns with no cell actually
+    /// covered (mirroring the adjacent-cell case in `span`), or when the
+    /// whole block has scrolled out of the `screen_lines` currently on
+    /// screen.
+    pub fn block_span(&self, screen_lines: Line) -> Option<BlockSpan> {
         match *self {
-            Selection::Active { ref start, ref end, ref start_side, ref end_side } => {
-                let (front, tail, front_side, tail_side) = if *start > *end {
-                    // Selected upward; start/end are swapped
-                    (end, start, end_side, start_side)
+            Selection::Block { ref start, ref end, ref start_side, ref end_side } => {
+                let (start, end) = match Anchor::resolve(start, end, screen_lines) {
+                    Some(points) => points,
+                    None => return None
+                };
+                let (ref start, ref end) = (start, end);
+
+                let (min_line, max_line) = if start.line <= end.line {
+                    (start.line, end.line)
                 } else {
-                    // Selected downward; no swapping
-                    (start, end, start_side, end_side)
+                    (end.line, start.line)
                 };
 
-                debug_assert!(!(tail < front));
+                let (left, left_side, right, right_side) = if start.col <= end.col {
+                    (start.col, *start_side, end.col, *end_side)
+                } else {
+                    (end.col, *end_side, start.col, *start_side)
+                };
 
-                // Single-cell selections are a special case
-                if start == end {
-                    if start_side != end_side {
-                        return Some(Span {
-                            ty: SpanType::Inclusive,
-                            front: *front,
-                            tail: *tail
-                        });
-                    } else {
+                // A left-edge cell clicked on its right half, or a right-edge
+                // cell clicked on its left half, doesn't belong to the
+                // selection; shrink the range in by one column from that side.
+                let min_col = if left_side == Side::Right { left + 1 } else { left };
+                let max_col = if right_side == Side::Left {
+                    if right == Column(0) {
                         return None;
                     }
-                }
+                    right - 1
+                } else {
+                    right
+                };
 
-                // The other special case is two adjacent cells with no
-                // selection: [ B][E ] or [ E][B ]
-                let adjacent = tail.line == front.line && tail.col - front.col == Column(1);
-                if adjacent && *front_side == Side::Right && *tail_side == Side::Left {
+                if min_col > max_col {
                     return None;
                 }
 
-                Some(match (*front_side, *tail_side) {
-                    // [FX][XX][XT]
-                    (Side::Left, Side::Right) => Span {
-                        front: *front,
-                        tail: *tail,
-                        ty: SpanType::Inclusive
-                    },
-                    // [ F][XX][T ]
-                    (Side::Right, Side::Left) => Span {
-                        front: *front,
-                        tail: *tail,
-                        ty: SpanType::Exclusive
-                    },
-                    // [FX][XX][T ]
-                    (Side::Left, Side::Left) => Span {
-                        front: *front,
-                        tail: *tail,
-                        ty: SpanType::ExcludeTail
-                    },
-                    // [ F][XX][XT]
-                    (Side::Right, Side::Right) => Span {
-                        front: *front,
-                        tail: *tail,
-                        ty: SpanType::ExcludeFront
-                    },
-                })
+                Some(BlockSpan { min_line, max_line, min_col, max_col })
             },
-            Selection::Empty => None
+            _ => None
         }
     }
+
+    /// Turn a pattern match under the clicked cell into a `Span`
+    ///
+    /// This is the state-layer half of click-to-select (e.g. double-clicking
+    /// a URL or file path). `matches` holds the inclusive `(start, end)`
+    /// endpoints of every match currently on screen, as found by whatever
+    /// regex/highlighter the caller uses; this module stays free of any
+    /// pattern-matching logic and just hands back the same kind of `Span`
+    /// a mouse drag would produce for the match covering `point`.
+    pub fn smart_select(point: Point, matches: &[(Point, Point)]) -> Option<Span> {
+        matches.iter()
+            .find(|&&(start, end)| start <= point && point <= end)
+            .map(|&(start, end)| Selection::span_from_points(start, end))
+    }
+
+    /// Build an inclusive `Span` from a match's endpoints
+    ///
+    /// Swaps `start`/`end` if necessary so the result is always ordered
+    /// front-to-tail, the same as a `Span` produced by dragging the mouse.
+    fn span_from_points(start: Point, end: Point) -> Span {
+        let (front, tail) = if start <= end { (start, end) } else { (end, start) };
+        Span { front, tail, ty: SpanType::Inclusive }
+    }
 }
 
 /// How to interpret the locations of a Span.
@@ -252,6 +603,34 @@ impl ToRange for Span {
     }
 }
 
+/// Represents the selected region of a block (rectangular) selection
+///
+/// Unlike `Span`, which describes one contiguous, wrap-aware range of
+/// cells, a `BlockSpan` covers the same `[min_col, max_col]` sub-range on
+/// every line from `min_line` to `max_line`, so it expands to one range
+/// per line rather than a single `Linear` range.
+#[derive(Debug, Eq, PartialEq)]
+pub struct BlockSpan {
+    min_line: Line,
+    max_line: Line,
+    min_col: Column,
+    max_col: Column,
+}
+
+impl BlockSpan {
+    /// Expand this span into one inclusive `Linear` range per selected line
+    pub fn to_ranges(&self, cols: Column) -> Vec<RangeInclusive<Linear>> {
+        let mut ranges = Vec::with_capacity(self.max_line.0 - self.min_line.0 + 1);
+
+        for line in self.min_line.0...self.max_line.0 {
+            let base = line * cols.0;
+            ranges.push(Linear(base + self.min_col.0)...Linear(base + self.max_col.0));
+        }
+
+        ranges
+    }
+}
+
 /// Tests for selection
 ///
 /// There are comments on all of the tests describing the selection. Pictograms
@@ -264,7 +643,7 @@ impl ToRange for Span {
 #[cfg(test)]
 mod test {
     use index::{Line, Column, Side, Point};
-    use super::{Selection, Span, SpanType};
+    use super::{Selection, Span, SpanType, BlockSpan};
 
     /// Test case of single cell selection
     ///
@@ -278,7 +657,7 @@ mod test {
         selection.update(location, Side::Left);
         selection.update(location, Side::Right);
 
-        assert_eq!(selection.span().unwrap(), Span {
+        assert_eq!(selection.span(Line(5), Column(5)).unwrap(), Span {
             ty: SpanType::Inclusive,
             front: location,
             tail: location
@@ -297,7 +676,7 @@ mod test {
         selection.update(location, Side::Right);
         selection.update(location, Side::Left);
 
-        assert_eq!(selection.span().unwrap(), Span {
+        assert_eq!(selection.span(Line(5), Column(5)).unwrap(), Span {
             ty: SpanType::Inclusive,
             front: location,
             tail: location
@@ -315,7 +694,7 @@ mod test {
         selection.update(Point::new(Line(0), Column(0)), Side::Right);
         selection.update(Point::new(Line(0), Column(1)), Side::Left);
 
-        assert_eq!(selection.span(), None);
+        assert_eq!(selection.span(Line(5), Column(5)), None);
     }
 
     /// Test adjacent cell selection from right to left
@@ -329,7 +708,7 @@ mod test {
         selection.update(Point::new(Line(0), Column(1)), Side::Left);
         selection.update(Point::new(Line(0), Column(0)), Side::Right);
 
-        assert_eq!(selection.span(), None);
+        assert_eq!(selection.span(Line(5), Column(5)), None);
     }
 
     /// Test selection across adjacent lines
@@ -347,7 +726,7 @@ mod test {
         selection.update(Point::new(Line(1), Column(1)), Side::Right);
         selection.update(Point::new(Line(0), Column(1)), Side::Right);
 
-        assert_eq!(selection.span().unwrap(), Span {
+        assert_eq!(selection.span(Line(5), Column(5)).unwrap(), Span {
             front: Point::new(Line(0), Column(1)),
             tail: Point::new(Line(1), Column(1)),
             ty: SpanType::ExcludeFront
@@ -372,10 +751,221 @@ mod test {
         selection.update(Point::new(Line(1), Column(1)), Side::Right);
         selection.update(Point::new(Line(1), Column(0)), Side::Right);
 
-        assert_eq!(selection.span().unwrap(), Span {
+        assert_eq!(selection.span(Line(5), Column(5)).unwrap(), Span {
             front: Point::new(Line(0), Column(1)),
             tail: Point::new(Line(1), Column(0)),
             ty: SpanType::ExcludeFront
         });
     }
+
+    /// Test a triple-click line selection snapping to the whole visual line
+    ///
+    /// 1. [  ][  ][  ][  ][  ]
+    /// 2. [  ][ B][  ][  ][  ]
+    /// 3. [XX][XX][XX][XX][XX]
+    #[test]
+    fn line_selection_snaps_to_full_line() {
+        let selection = Selection::lines(Point::new(Line(0), Column(1)));
+
+        assert_eq!(selection.span(Line(5), Column(5)).unwrap(), Span {
+            front: Point::new(Line(0), Column(0)),
+            tail: Point::new(Line(0), Column(4)),
+            ty: SpanType::Inclusive
+        });
+    }
+
+    /// Test a double-click word selection expanding outward to separators
+    /// on either side, then extended by dragging into the next word
+    ///
+    /// Row: `foo bar baz`, spaces are separators.
+    ///
+    /// 1. double-click lands in "bar" -> selects "bar"
+    /// 2. drag onto "baz" -> selection grows to cover "bar baz"
+    #[test]
+    fn semantic_selection_expands_to_words() {
+        let is_separator = |point: Point| {
+            point.col == Column(3) || point.col == Column(7)
+        };
+
+        let mut selection = Selection::semantic(Point::new(Line(0), Column(5)));
+        assert_eq!(selection.span_with(Line(5), Column(11), is_separator).unwrap(), Span {
+            front: Point::new(Line(0), Column(4)),
+            tail: Point::new(Line(0), Column(6)),
+            ty: SpanType::Inclusive
+        });
+
+        selection.update(Point::new(Line(0), Column(9)), Side::Right);
+        assert_eq!(selection.span_with(Line(5), Column(11), is_separator).unwrap(), Span {
+            front: Point::new(Line(0), Column(4)),
+            tail: Point::new(Line(0), Column(10)),
+            ty: SpanType::Inclusive
+        });
+    }
+
+    /// Test block selection across a 2x2 region, top-left to bottom-right
+    ///
+    /// 1. [  ][  ]
+    ///    [  ][  ]
+    /// 2. [ B][  ]
+    ///    [  ][  ]
+    /// 3. [ B][XX]
+    ///    [  ][XE]
+    #[test]
+    fn block_selection_2x2_downward() {
+        let mut selection = Selection::Empty;
+        selection.update_block(Point::new(Line(0), Column(0)), Side::Right);
+        selection.update_block(Point::new(Line(1), Column(1)), Side::Right);
+
+        let span = selection.block_span(Line(5)).unwrap();
+        assert_eq!(span, BlockSpan {
+            min_line: Line(0),
+            max_line: Line(1),
+            min_col: Column(1),
+            max_col: Column(1)
+        });
+    }
+
+    /// Test block selection started from the bottom-right, dragged upward
+    /// and to the left, with both endpoints clicked on the left half of
+    /// their cell
+    ///
+    /// 1. [  ][  ]
+    ///    [  ][  ]
+    /// 2. [  ][  ]
+    ///    [  ][B ]
+    /// 3. [EX][  ]
+    ///    [XX][B ]
+    #[test]
+    fn block_selection_2x2_upward_reversed() {
+        let mut selection = Selection::Empty;
+        selection.update_block(Point::new(Line(1), Column(1)), Side::Left);
+        selection.update_block(Point::new(Line(0), Column(0)), Side::Left);
+
+        let span = selection.block_span(Line(5)).unwrap();
+        assert_eq!(span, BlockSpan {
+            min_line: Line(0),
+            max_line: Line(1),
+            min_col: Column(0),
+            max_col: Column(0)
+        });
+    }
+
+    /// Test that a selection stays anchored to its text as the screen
+    /// scrolls, rather than to its original screen coordinates
+    ///
+    /// Before scrolling:
+    /// 1.  [  ][  ][  ][  ][  ]
+    ///     [  ][  ][  ][  ][  ]
+    ///     [  ][BX][XX][XE][  ]
+    ///
+    /// After scrolling 2 lines into the scrollback, the same text is now on
+    /// the top row:
+    /// 2.  [  ][BX][XX][XE][  ]
+    ///     [  ][  ][  ][  ][  ]
+    ///     [  ][  ][  ][  ][  ]
+    #[test]
+    fn selection_follows_content_when_grid_scrolls() {
+        let mut selection = Selection::Empty;
+        selection.update(Point::new(Line(2), Column(1)), Side::Left);
+        selection.update(Point::new(Line(2), Column(3)), Side::Right);
+
+        selection.rotate(2);
+
+        assert_eq!(selection.span(Line(5), Column(5)).unwrap(), Span {
+            front: Point::new(Line(0), Column(1)),
+            tail: Point::new(Line(0), Column(3)),
+            ty: SpanType::Inclusive
+        });
+    }
+
+    /// Test that a selection scrolled entirely above the visible screen
+    /// no longer produces a span
+    #[test]
+    fn selection_scrolled_off_screen_has_no_span() {
+        let mut selection = Selection::Empty;
+        selection.update(Point::new(Line(0), Column(0)), Side::Left);
+        selection.update(Point::new(Line(0), Column(2)), Side::Right);
+
+        selection.rotate(3);
+
+        assert_eq!(selection.span(Line(5), Column(5)), None);
+    }
+
+    /// Test that a selection with only one endpoint scrolled off screen
+    /// still produces a span covering its visible portion
+    ///
+    /// Before scrolling (5-row screen):
+    /// 1.  [  ][B ][  ][  ][  ]
+    ///     [  ][  ][  ][  ][  ]
+    ///     [  ][  ][  ][  ][  ]
+    ///     [  ][  ][  ][  ][  ]
+    ///     [  ][  ][ E][  ][  ]
+    ///
+    /// After scrolling 2 lines into the scrollback, `B` has scrolled off
+    /// the top; the visible portion still runs from the top of the screen
+    /// down to `E`:
+    /// 2.  [XX][XX][XX][XX][XX]  <- off-screen B snaps to the start of this row
+    ///     [XX][XX][XX][XX][XX]
+    ///     [XX][XX][XE][  ][  ]
+    #[test]
+    fn selection_partially_scrolled_off_screen_shows_visible_portion() {
+        let mut selection = Selection::Empty;
+        selection.update(Point::new(Line(0), Column(1)), Side::Left);
+        selection.update(Point::new(Line(4), Column(2)), Side::Right);
+
+        selection.rotate(2);
+
+        assert_eq!(selection.span(Line(5), Column(5)).unwrap(), Span {
+            front: Point::new(Line(0), Column(0)),
+            tail: Point::new(Line(2), Column(2)),
+            ty: SpanType::Inclusive
+        });
+    }
+
+    /// Test that a block selection with only one endpoint scrolled off
+    /// screen still produces a span covering its visible rows
+    #[test]
+    fn block_selection_partially_scrolled_off_screen_shows_visible_portion() {
+        let mut selection = Selection::Empty;
+        selection.update_block(Point::new(Line(0), Column(0)), Side::Right);
+        selection.update_block(Point::new(Line(4), Column(2)), Side::Right);
+
+        selection.rotate(2);
+
+        let span = selection.block_span(Line(5)).unwrap();
+        assert_eq!(span, BlockSpan {
+            min_line: Line(0),
+            max_line: Line(2),
+            min_col: Column(1),
+            max_col: Column(2)
+        });
+    }
+
+    /// Test that clicking inside a precomputed match selects the whole match
+    ///
+    /// 1. [  ][  ][  ][  ][  ]
+    /// 2. [  ][XX][XC][XX][  ]  (C is the clicked cell)
+    #[test]
+    fn smart_select_picks_the_match_under_the_click() {
+        let matches = [
+            (Point::new(Line(0), Column(1)), Point::new(Line(0), Column(3))),
+        ];
+
+        let span = Selection::smart_select(Point::new(Line(0), Column(2)), &matches).unwrap();
+        assert_eq!(span, Span {
+            front: Point::new(Line(0), Column(1)),
+            tail: Point::new(Line(0), Column(3)),
+            ty: SpanType::Inclusive
+        });
+    }
+
+    /// Test that clicking outside of every match produces no selection
+    #[test]
+    fn smart_select_misses_outside_any_match() {
+        let matches = [
+            (Point::new(Line(0), Column(1)), Point::new(Line(0), Column(3))),
+        ];
+
+        assert_eq!(Selection::smart_select(Point::new(Line(0), Column(4)), &matches), None);
+    }
 }